@@ -4,18 +4,64 @@
 // LICENSE file in the root directory of this source tree.
 
 use winter_air::{
-    Air, AirContext, Assertion, EvaluationFrame, ProofOptions, TraceInfo,
-    TransitionConstraintDegree,
+    Air, AirContext, Assertion, AuxTraceRandElements, EvaluationFrame, ProofOptions, TraceInfo,
+    TraceLayout, TransitionConstraintDegree,
 };
-use winter_math::FieldElement;
-use winter_prover::{Trace, TraceTable};
-use winter_utils::{ByteWriter, Serializable};
+use winter_math::{ExtensionOf, FieldElement, StarkField};
+use winter_prover::{ColMatrix, Trace, TraceTable};
+use winter_utils::{ByteWriter, Deserializable, Serializable, SliceReader};
 
 use base64::{decode, encode};
-use clap::Args;
+use clap::{ArgEnum, Args};
 use serde::{Deserialize, Serialize};
 
-pub type BaseElement = winter_math::fields::f128::BaseElement;
+pub type F62 = winter_math::fields::f62::BaseElement;
+pub type F64 = winter_math::fields::f64::BaseElement;
+pub type F128 = winter_math::fields::f128::BaseElement;
+
+/// Base field a proof was generated over, selected via `--field`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum)]
+pub enum EnumField {
+    F62,
+    F64,
+    F128,
+}
+
+impl EnumField {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnumField::F62 => "f62",
+            EnumField::F64 => "f64",
+            EnumField::F128 => "f128",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "f62" => EnumField::F62,
+            "f64" => EnumField::F64,
+            "f128" => EnumField::F128,
+            _ => panic!("unknown field tag: {}", name),
+        }
+    }
+}
+
+/// Tags a concrete [`StarkField`] with its [`EnumField`] variant.
+pub trait FieldTag: StarkField {
+    const FIELD: EnumField;
+}
+
+impl FieldTag for F62 {
+    const FIELD: EnumField = EnumField::F62;
+}
+
+impl FieldTag for F64 {
+    const FIELD: EnumField = EnumField::F64;
+}
+
+impl FieldTag for F128 {
+    const FIELD: EnumField = EnumField::F128;
+}
 
 #[derive(Args, Debug)]
 #[clap(next_help_heading = "INPUT ARGUMENTS")]
@@ -26,57 +72,291 @@ pub struct InputArg {
     pub n: usize,
 }
 
-pub struct PublicInputs {
-    pub start: BaseElement,
-    pub result: BaseElement,
+pub struct PublicInputs<B: StarkField> {
+    pub start: B,
+    pub result: B,
+    /// Cycle length of [`FreshAir`]'s periodic selector column. See `--period`.
+    pub period: usize,
+}
+
+/// Checks the `--period`/`--n` invariant the periodic column relies on: `period`
+/// must be a power of two that divides `n`, and `n - 2` must also fall on a
+/// selector-masked row so the last trace row gets a fresh value instead of a
+/// held-over one.
+pub fn validate_period(period: usize, n: usize) -> Result<(), String> {
+    if period == 0 || !period.is_power_of_two() {
+        return Err(format!("--period must be a power of two, got {}", period));
+    }
+    if period > n || n % period != 0 || (n - 2) % period != 0 {
+        return Err(format!(
+            "--period ({}) must divide --n ({}) evenly and align with the last row",
+            period, n
+        ));
+    }
+    Ok(())
 }
 
-impl Serializable for PublicInputs {
+impl<B: StarkField> Serializable for PublicInputs<B> {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write(self.start);
         target.write(self.result);
+        target.write(self.period as u64);
     }
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Data {
+    pub field: String,
     pub start: String,
     pub result: String,
+    pub period: usize,
     pub proof: String,
 }
 
 impl ::std::default::Default for Data {
     fn default() -> Self {
         Self {
-            start: "0".into(),
-            result: "0".into(),
+            field: EnumField::F128.name().into(),
+            start: "".into(),
+            result: "".into(),
+            period: 1,
             proof: "".into(),
         }
     }
 }
 
-pub fn from_data(data: Data) -> (PublicInputs, Vec<u8>) {
+// `start`/`result` are base64-encoded field-element bytes rather than decimal
+// strings, so they're opaque in the text formats too, not just `Bin`.
+pub fn from_data<B: StarkField>(data: Data) -> (PublicInputs<B>, Vec<u8>) {
+    let start = decode(data.start).unwrap();
+    let result = decode(data.result).unwrap();
     (
         PublicInputs {
-            start: BaseElement::new(data.start.parse().unwrap()),
-            result: BaseElement::new(data.result.parse().unwrap()),
+            start: B::read_from(&mut SliceReader::new(&start)).unwrap(),
+            result: B::read_from(&mut SliceReader::new(&result)).unwrap(),
+            period: data.period,
         },
         decode(data.proof).unwrap(),
     )
 }
 
-pub fn to_data(proof: Vec<u8>, public_input: PublicInputs) -> Data {
+pub fn to_data<B: FieldTag>(proof: Vec<u8>, public_input: PublicInputs<B>) -> Data {
     Data {
-        start: public_input.start.to_string(),
-        result: public_input.result.to_string(),
+        field: B::FIELD.name().into(),
+        start: encode(public_input.start.to_bytes()),
+        result: encode(public_input.result.to_bytes()),
+        period: public_input.period,
         proof: encode(proof),
     }
 }
 
-pub type TraceType = TraceTable<BaseElement>;
+/// On-disk encoding for the proving/verifying artifact. `Bin` bypasses [`Data`]
+/// and writes the raw proof bytes with a length-prefixed header instead.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ArgEnum)]
+pub enum EnumFormat {
+    Toml,
+    Json,
+    Cbor,
+    Bin,
+}
+
+pub fn save_data(data: &Data, path: &str, format: EnumFormat) {
+    match format {
+        EnumFormat::Toml => {
+            std::fs::write(path, toml::to_string(data).expect("failed to serialize as toml"))
+        }
+        EnumFormat::Json => std::fs::write(
+            path,
+            serde_json::to_string_pretty(data).expect("failed to serialize as json"),
+        ),
+        EnumFormat::Cbor => {
+            std::fs::write(path, serde_cbor::to_vec(data).expect("failed to serialize as cbor"))
+        }
+        EnumFormat::Bin => unreachable!("bin format is written via write_bin"),
+    }
+    .expect("failed to write proof file")
+}
+
+pub fn load_data(path: &str, format: EnumFormat) -> Data {
+    match format {
+        EnumFormat::Toml => toml::from_str(&read_to_string(path)).expect("failed to parse toml"),
+        EnumFormat::Json => serde_json::from_str(&read_to_string(path)).expect("failed to parse json"),
+        EnumFormat::Cbor => {
+            serde_cbor::from_slice(&read_bytes(path)).expect("failed to parse cbor")
+        }
+        EnumFormat::Bin => unreachable!("bin format is read via read_bin"),
+    }
+}
+
+fn read_to_string(path: &str) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|e| panic!("failed to read {}: {}", path, e))
+}
+
+fn read_bytes(path: &str) -> Vec<u8> {
+    std::fs::read(path).unwrap_or_else(|e| panic!("failed to read {}: {}", path, e))
+}
+
+/// Encodes a single proof as `len(field) || field || len(start) || start ||
+/// len(result) || result || period || len(proof) || proof`, with every length
+/// (and `period`) a little-endian `u32`/`u64`. No base64, no TOML/JSON/CBOR
+/// wrapper.
+fn encode_bin_record<B: FieldTag>(proof: &[u8], public_input: &PublicInputs<B>) -> Vec<u8> {
+    let field_name = B::FIELD.name().as_bytes();
+    let start_bytes = public_input.start.to_bytes();
+    let result_bytes = public_input.result.to_bytes();
+
+    let mut out = Vec::with_capacity(
+        24 + field_name.len() + start_bytes.len() + result_bytes.len() + proof.len(),
+    );
+    write_chunk(&mut out, field_name);
+    write_chunk(&mut out, &start_bytes);
+    write_chunk(&mut out, &result_bytes);
+    out.extend_from_slice(&(public_input.period as u64).to_le_bytes());
+    write_chunk(&mut out, proof);
+    out
+}
+
+/// Inverse of [`encode_bin_record`]; returns the decoded record plus the
+/// offset of the first byte following it, so callers can decode several
+/// records back to back (see [`read_bin_batch`]).
+fn decode_bin_record<B: StarkField>(bytes: &[u8], offset: usize) -> (PublicInputs<B>, Vec<u8>, usize) {
+    let (field_name, offset) = read_chunk(bytes, offset);
+    let _ = field_name;
+    let (start_bytes, offset) = read_chunk(bytes, offset);
+    let start = B::read_from(&mut SliceReader::new(start_bytes)).unwrap();
+    let (result_bytes, offset) = read_chunk(bytes, offset);
+    let result = B::read_from(&mut SliceReader::new(result_bytes)).unwrap();
+    let period = u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap()) as usize;
+    let (proof, offset) = read_chunk(bytes, offset + 8);
+
+    (
+        PublicInputs {
+            start,
+            result,
+            period,
+        },
+        proof.to_vec(),
+        offset,
+    )
+}
+
+pub fn write_bin<B: FieldTag>(path: &str, proof: &[u8], public_input: &PublicInputs<B>) {
+    std::fs::write(path, encode_bin_record(proof, public_input)).expect("failed to write proof file");
+}
+
+pub fn read_bin_field(path: &str) -> EnumField {
+    let bytes = read_bytes(path);
+    let (field_name, _) = read_chunk(&bytes, 0);
+    EnumField::from_name(std::str::from_utf8(field_name).expect("field tag is not valid utf-8"))
+}
+
+/// Same as [`read_bin_field`], but for a `--batch` file, whose first 4 bytes
+/// are the record count written by [`write_bin_batch`] rather than the first
+/// record's field-name chunk.
+pub fn read_bin_batch_field(path: &str) -> EnumField {
+    let bytes = read_bytes(path);
+    let (field_name, _) = read_chunk(&bytes, 4);
+    EnumField::from_name(std::str::from_utf8(field_name).expect("field tag is not valid utf-8"))
+}
+
+pub fn read_bin<B: StarkField>(path: &str) -> (PublicInputs<B>, Vec<u8>) {
+    let bytes = read_bytes(path);
+    let (pub_inputs, proof, _) = decode_bin_record(&bytes, 0);
+    (pub_inputs, proof)
+}
+
+/// Writes every proof produced by a `--batch` run into a single file as
+/// `count || record_0 || record_1 || ...`, each record encoded as in
+/// [`encode_bin_record`].
+pub fn write_bin_batch<B: FieldTag>(
+    path: &str,
+    proofs: &[(Vec<u8>, PublicInputs<B>)],
+) {
+    let mut out = Vec::new();
+    out.extend_from_slice(&(proofs.len() as u32).to_le_bytes());
+    for (proof, public_input) in proofs {
+        out.extend_from_slice(&encode_bin_record(proof, public_input));
+    }
+    std::fs::write(path, out).expect("failed to write batch proof file");
+}
+
+pub fn read_bin_batch<B: StarkField>(path: &str) -> Vec<(PublicInputs<B>, Vec<u8>)> {
+    let bytes = read_bytes(path);
+    let count = u32::from_le_bytes(bytes[0..4].try_into().unwrap()) as usize;
+    let mut offset = 4;
+    let mut records = Vec::with_capacity(count);
+    for _ in 0..count {
+        let (pub_inputs, proof, next_offset) = decode_bin_record(&bytes, offset);
+        records.push((pub_inputs, proof));
+        offset = next_offset;
+    }
+    records
+}
+
+fn write_chunk(out: &mut Vec<u8>, chunk: &[u8]) {
+    out.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+    out.extend_from_slice(chunk);
+}
+
+fn read_chunk(bytes: &[u8], offset: usize) -> (&[u8], usize) {
+    let len = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+    let start = offset + 4;
+    (&bytes[start..start + len], start + len)
+}
+
+/// A file-level array of [`Data`] records, used by `--batch` proving/verifying.
+/// TOML requires a top-level table rather than a bare array, so this wraps the
+/// array in `proofs` for all three serde formats (TOML, JSON and CBOR).
+#[derive(Serialize, Deserialize, Default)]
+pub struct BatchData {
+    pub proofs: Vec<Data>,
+}
+
+pub fn save_batch_data(data: &BatchData, path: &str, format: EnumFormat) {
+    match format {
+        EnumFormat::Toml => {
+            std::fs::write(path, toml::to_string(data).expect("failed to serialize as toml"))
+        }
+        EnumFormat::Json => std::fs::write(
+            path,
+            serde_json::to_string_pretty(data).expect("failed to serialize as json"),
+        ),
+        EnumFormat::Cbor => {
+            std::fs::write(path, serde_cbor::to_vec(data).expect("failed to serialize as cbor"))
+        }
+        EnumFormat::Bin => unreachable!("bin format is written via write_bin_batch"),
+    }
+    .expect("failed to write batch proof file")
+}
+
+pub fn load_batch_data(path: &str, format: EnumFormat) -> BatchData {
+    match format {
+        EnumFormat::Toml => toml::from_str(&read_to_string(path)).expect("failed to parse toml"),
+        EnumFormat::Json => serde_json::from_str(&read_to_string(path)).expect("failed to parse json"),
+        EnumFormat::Cbor => {
+            serde_cbor::from_slice(&read_bytes(path)).expect("failed to parse cbor")
+        }
+        EnumFormat::Bin => unreachable!("bin format is read via read_bin_batch"),
+    }
+}
+
+pub type TraceType<B> = TraceTable<B>;
 
-pub fn build_trace(arg: &InputArg) -> TraceType {
-    let trace_width = 4;
+/// Main-trace columns populated by [`build_trace_with_aux`]; `COL_A`/`COL_B`
+/// are a permutation of one another.
+const COL_A: usize = 4;
+const COL_B: usize = 5;
+
+pub fn build_trace<B: StarkField + From<u128>>(arg: &InputArg, period: usize) -> TraceType<B> {
+    build_trace_impl(arg, 4, period)
+}
+
+/// Advances the real transition once every `period` rows, holding state on the rest.
+fn build_trace_impl<B: StarkField + From<u128>>(
+    arg: &InputArg,
+    trace_width: usize,
+    period: usize,
+) -> TraceType<B> {
     let mut trace = TraceTable::new(trace_width, arg.n);
 
     trace.fill(
@@ -84,29 +364,39 @@ pub fn build_trace(arg: &InputArg) -> TraceType {
             let a = arg.start;
             let b = arg.n as u128;
             if a > b {
-                state[0] = BaseElement::from(a - b);
+                state[0] = B::from(a - b);
             } else {
-                state[0] = BaseElement::from(b - a);
+                state[0] = B::from(b - a);
             }
-            state[1] = BaseElement::from(a + 1);
-            state[2] = BaseElement::from(b - 1);
+            state[1] = B::from(a + 1);
+            state[2] = B::from(b - 1);
             if a + 1 > b - 1 {
-                state[3] = BaseElement::ONE;
+                state[3] = B::ONE;
             } else {
-                state[3] = BaseElement::ZERO;
+                state[3] = B::ZERO;
             }
             println!("trace.fill.init: step:0 state:{:?}", state);
         },
         |last_step, state| {
+            if last_step % period != 0 {
+                // row not covered by the selector: state stays unchanged
+                println!(
+                    "trace.fill.hold: step:{} state:{:?}",
+                    last_step + 1,
+                    state
+                );
+                return;
+            }
+
             state[0] = state[3] * (state[1] - state[2])
-                + (BaseElement::ONE - state[3]) * (state[2] - state[1]);
-            let next: u128 = last_step as u128 + 2;
-            state[1] = BaseElement::from(arg.start + next);
-            state[2] = BaseElement::from(arg.n as u128 - next);
+                + (B::ONE - state[3]) * (state[2] - state[1]);
+            let next: u128 = (last_step / period) as u128 + 2;
+            state[1] = B::from(arg.start + next);
+            state[2] = B::from(arg.n as u128 - next);
             if (arg.start + next) > (arg.n as u128 - next) {
-                state[3] = BaseElement::ONE;
+                state[3] = B::ONE;
             } else {
-                state[3] = BaseElement::ZERO;
+                state[3] = B::ZERO;
             }
             println!(
                 "trace.fill.update: step:{} state:{:?}",
@@ -119,34 +409,143 @@ pub fn build_trace(arg: &InputArg) -> TraceType {
     trace
 }
 
-pub fn get_pub_inputs(trace: &TraceType) -> PublicInputs {
+pub fn get_pub_inputs<B: StarkField>(trace: &TraceType<B>, period: usize) -> PublicInputs<B> {
     let last_step = trace.length() - 1;
     PublicInputs {
         start: trace.get(0, 0),
         result: trace.get(0, last_step),
+        period,
+    }
+}
+
+/// A [`TraceTable`] wrapper that derives the auxiliary running-product column
+/// `z[i+1] = z[i] * (col_a[i] + alpha) / (col_b[i] + alpha)`, which collapses
+/// to 1 at the last step iff `COL_A` and `COL_B` hold the same multiset over
+/// every row but the last (the last row's values never enter the product).
+pub struct RapTraceTable<B: StarkField> {
+    layout: TraceLayout,
+    trace: TraceTable<B>,
+}
+
+impl<B: StarkField> RapTraceTable<B> {
+    fn new(trace: TraceTable<B>) -> Self {
+        let layout = TraceLayout::new(trace.width(), [1], [1]);
+        RapTraceTable { layout, trace }
+    }
+
+    pub fn get_pub_inputs(&self, period: usize) -> PublicInputs<B> {
+        get_pub_inputs(&self.trace, period)
+    }
+}
+
+impl<B: StarkField> Trace for RapTraceTable<B> {
+    type BaseField = B;
+
+    fn layout(&self) -> &TraceLayout {
+        &self.layout
+    }
+
+    fn length(&self) -> usize {
+        self.trace.length()
+    }
+
+    fn meta(&self) -> &[u8] {
+        &[]
+    }
+
+    fn main_segment(&self) -> &ColMatrix<B> {
+        self.trace.main_segment()
+    }
+
+    fn read_main_frame(&self, row_idx: usize, frame: &mut EvaluationFrame<B>) {
+        self.trace.read_main_frame(row_idx, frame);
+    }
+
+    fn build_aux_segment<E: FieldElement<BaseField = B>>(
+        &mut self,
+        aux_segments: &[ColMatrix<E>],
+        rand_elements: &[E],
+    ) -> Option<ColMatrix<E>> {
+        if !aux_segments.is_empty() {
+            return None;
+        }
+
+        let alpha = rand_elements[0];
+        let main_segment = self.trace.main_segment();
+        let mut z = Vec::with_capacity(self.length());
+        z.push(E::ONE);
+        for i in 0..self.length() - 1 {
+            let col_a = main_segment.get(COL_A, i).into();
+            let col_b = main_segment.get(COL_B, i).into();
+            let ratio = (col_a + alpha) / (col_b + alpha);
+            z.push(z[i] * ratio);
+        }
+
+        Some(ColMatrix::new(vec![z]))
     }
 }
 
-pub struct FreshAir {
-    context: AirContext<BaseElement>,
-    start: BaseElement,
-    result: BaseElement,
+/// Builds the base execution trace plus `COL_A`/`COL_B`, wrapped so the
+/// auxiliary running-product column can be derived from it.
+pub fn build_trace_with_aux<B: StarkField + From<u128>>(
+    arg: &InputArg,
+    period: usize,
+) -> RapTraceTable<B> {
+    let mut trace = build_trace_impl::<B>(arg, COL_B + 1, period);
+    let n = trace.length();
+    for i in 0..n - 1 {
+        trace.set(COL_A, i, B::from(i as u128));
+        trace.set(COL_B, i, B::from((n - 2 - i) as u128));
+    }
+    trace.set(COL_A, n - 1, B::from((n - 1) as u128));
+    trace.set(COL_B, n - 1, B::from((n - 1) as u128));
+
+    RapTraceTable::new(trace)
 }
 
-impl Air for FreshAir {
-    type BaseField = BaseElement;
-    type PublicInputs = PublicInputs;
+pub struct FreshAir<B: StarkField> {
+    context: AirContext<B>,
+    start: B,
+    result: B,
+    /// Cycle length of the periodic selector column.
+    period: usize,
+}
 
-    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs, options: ProofOptions) -> Self {
-        assert_eq!(4, trace_info.width());
+impl<B: StarkField> Air for FreshAir<B> {
+    type BaseField = B;
+    type PublicInputs = PublicInputs<B>;
 
-        let degrees = vec![TransitionConstraintDegree::new(2)];
+    fn new(trace_info: TraceInfo, pub_inputs: PublicInputs<B>, options: ProofOptions) -> Self {
+        // second constraint pins the state on rows the selector doesn't cover
+        let main_degrees = vec![
+            TransitionConstraintDegree::with_cycles(2, vec![pub_inputs.period]),
+            TransitionConstraintDegree::with_cycles(1, vec![pub_inputs.period]),
+        ];
         let num_assertions = 2;
 
+        let context = if trace_info.layout().num_aux_segments() == 0 {
+            assert_eq!(4, trace_info.width());
+            AirContext::new(trace_info, main_degrees, num_assertions, options)
+        } else {
+            assert_eq!(COL_B + 1, trace_info.width());
+            // aux running-product column: degree-2 transition, two boundary assertions
+            let aux_degrees = vec![TransitionConstraintDegree::new(2)];
+            let num_aux_assertions = 2;
+            AirContext::new_multi_segment(
+                trace_info,
+                main_degrees,
+                aux_degrees,
+                num_assertions,
+                num_aux_assertions,
+                options,
+            )
+        };
+
         FreshAir {
-            context: AirContext::new(trace_info, degrees, num_assertions, options),
+            context,
             start: pub_inputs.start,
             result: pub_inputs.result,
+            period: pub_inputs.period,
         }
     }
 
@@ -157,19 +556,23 @@ impl Air for FreshAir {
     fn evaluate_transition<E: FieldElement + From<Self::BaseField>>(
         &self,
         frame: &EvaluationFrame<E>,
-        _periodic_values: &[E],
+        periodic_values: &[E],
         result: &mut [E],
     ) {
         let current = frame.current();
         let next;
         next = current[3] * (current[1] - current[2])
             + (E::ONE - current[3]) * (current[2] - current[1]);
-        result[0] = frame.next()[0] - next;
-        // println!(
-        //     "evaluate_transition: current:{:?} next:{:?}",
-        //     current,
-        //     frame.next()
-        // );
+        // recurrence only enforced on masked-in rows; hold constraint covers the rest
+        let selector = periodic_values[0];
+        result[0] = selector * (frame.next()[0] - next);
+        result[1] = (E::ONE - selector) * (frame.next()[0] - current[0]);
+    }
+
+    fn get_periodic_column_values(&self) -> Vec<Vec<Self::BaseField>> {
+        let mut mask = vec![Self::BaseField::ZERO; self.period];
+        mask[0] = Self::BaseField::ONE;
+        vec![mask]
     }
 
     fn get_assertions(&self) -> Vec<Assertion<Self::BaseField>> {
@@ -179,4 +582,36 @@ impl Air for FreshAir {
             Assertion::single(0, last_step, self.result),
         ]
     }
+
+    fn evaluate_aux_transition<F, E>(
+        &self,
+        main_frame: &EvaluationFrame<F>,
+        aux_frame: &EvaluationFrame<E>,
+        _periodic_values: &[F],
+        aux_rand_elements: &AuxTraceRandElements<E>,
+        result: &mut [E],
+    ) where
+        F: FieldElement<BaseField = Self::BaseField>,
+        E: FieldElement<BaseField = Self::BaseField> + ExtensionOf<F>,
+    {
+        let alpha = aux_rand_elements.get_segment_elements(0)[0];
+
+        let main_current = main_frame.current();
+        let z_current = aux_frame.current()[0];
+        let z_next = aux_frame.next()[0];
+
+        result[0] = z_next * (main_current[COL_B].into() + alpha)
+            - z_current * (main_current[COL_A].into() + alpha);
+    }
+
+    fn get_aux_assertions<E: FieldElement<BaseField = Self::BaseField>>(
+        &self,
+        _aux_rand_elements: &AuxTraceRandElements<E>,
+    ) -> Vec<Assertion<E>> {
+        let last_step = self.trace_length() - 1;
+        vec![
+            Assertion::single(0, 0, E::ONE),
+            Assertion::single(0, last_step, E::ONE),
+        ]
+    }
 }
@@ -8,12 +8,17 @@ use std::time::Instant;
 
 use log::debug;
 use winter_air::{FieldExtension, HashFunction, ProofOptions};
-use winter_math::log2;
+use winter_math::{log2, StarkField};
 use winter_prover::{Prover, StarkProof, Trace};
 
 pub mod air;
-use air::{build_trace, get_pub_inputs, to_data};
-use air::{BaseElement, FreshAir, InputArg, PublicInputs, TraceType};
+use air::{
+    build_trace, build_trace_with_aux, get_pub_inputs, save_data, to_data, validate_period,
+    write_bin, write_bin_batch,
+};
+use air::{
+    EnumField, EnumFormat, FieldTag, FreshAir, InputArg, PublicInputs, RapTraceTable, TraceType,
+};
 
 use clap::{ArgEnum, Args, Parser};
 
@@ -22,6 +27,36 @@ use clap::{ArgEnum, Args, Parser};
 struct Cli {
     #[clap(long, short, display_order = 1, default_value_t = String::from("./stark.toml"))]
     proof_file_path: String,
+    /// On-disk encoding for the proof file.
+    #[clap(long, arg_enum, display_order = 2, default_value_t = EnumFormat::Toml)]
+    format: EnumFormat,
+    /// Base field to run the computation over.
+    #[clap(long, arg_enum, display_order = 3, default_value_t = EnumField::F128)]
+    field: EnumField,
+    /// Prove an auxiliary (randomized-AIR) segment showing that two main-trace
+    /// columns are permutations of each other, in addition to the base trace.
+    #[clap(long, display_order = 4)]
+    aux: bool,
+    /// Enforce the main transition constraint only once every `period` steps,
+    /// via a periodic selector column (must be a power of two dividing `n`,
+    /// with `n - 2` also aligned to a selector row).
+    #[clap(long, display_order = 5, default_value_t = 1)]
+    period: usize,
+    /// Prove every `--batch-start`/`--batch-n` entry in one run and write the
+    /// resulting proofs into a single file as an array. Concurrency is bounded
+    /// to the number of available CPUs so large batches don't spawn one
+    /// CPU/memory-heavy proving thread per entry at once.
+    #[clap(long, display_order = 6)]
+    batch: bool,
+    /// Comma-separated list of `start` values to prove when `--batch` is set;
+    /// must be non-empty.
+    #[clap(long, display_order = 7, value_delimiter = ',')]
+    batch_start: Vec<u128>,
+    /// Comma-separated list of `n` values, one per `--batch-start` entry, so
+    /// each item in the batch can use a different trace length. If omitted,
+    /// every item uses `--n`.
+    #[clap(long, display_order = 8, value_delimiter = ',')]
+    batch_n: Vec<usize>,
     #[clap(flatten)]
     proof_options: ProofOptionsConfig,
     #[clap(flatten)]
@@ -42,7 +77,7 @@ enum EnumHashFunction {
     SHA3_256,
 }
 
-#[derive(Args)]
+#[derive(Args, Clone)]
 #[clap(next_help_heading = "PROOF OPTIONS")]
 struct ProofOptionsConfig {
     #[clap(long, default_value_t = 42)]
@@ -84,27 +119,50 @@ fn new_proof_options(opt: &ProofOptionsConfig) -> ProofOptions {
     )
 }
 
-struct ProveOutput {
+struct ProveOutput<B: StarkField> {
     proof: StarkProof,
-    public_input: PublicInputs,
+    public_input: PublicInputs<B>,
 }
 
-fn prove(cli: &Cli) -> ProveOutput {
+fn prove<B: FieldTag + From<u128>>(cli: &Cli, input_args: &InputArg) -> ProveOutput<B> {
     // generate the execution trace
     debug!(
         "Generating proof for computing a test algorithm with input_args {:?} \n\
         ---------------------",
-        cli.input_args
+        input_args
     );
 
+    if cli.aux {
+        let prover = FreshRapProver::<B> {
+            options: new_proof_options(&cli.proof_options),
+            period: cli.period,
+        };
+
+        let now = Instant::now();
+        let trace = build_trace_with_aux::<B>(input_args, cli.period);
+        let public_input = prover.get_pub_inputs(&trace);
+        let trace_length = trace.length();
+        debug!(
+            "Generated execution trace (with auxiliary segment) of 2^{} steps in {} ms",
+            log2(trace_length),
+            now.elapsed().as_millis()
+        );
+
+        return ProveOutput {
+            proof: prover.prove(trace).unwrap(),
+            public_input,
+        };
+    }
+
     // create a prover
-    let prover = FreshProver {
+    let prover = FreshProver::<B> {
         options: new_proof_options(&cli.proof_options),
+        period: cli.period,
     };
 
     // generate the execution trace
     let now = Instant::now();
-    let trace = build_trace(&cli.input_args);
+    let trace = build_trace::<B>(input_args, cli.period);
     let public_input = prover.get_pub_inputs(&trace);
     let trace_length = trace.length();
     debug!(
@@ -121,21 +179,22 @@ fn prove(cli: &Cli) -> ProveOutput {
     }
 }
 
-pub struct FreshProver {
+pub struct FreshProver<B: StarkField> {
     options: ProofOptions,
+    period: usize,
 }
 
 // When implementing Prover trait we set the `Air` associated type to the AIR of the
 // computation we defined previously, and set the `Trace` associated type to `TraceTable`
 // struct as we don't need to define a custom trace for our computation.
-impl Prover for FreshProver {
-    type BaseField = BaseElement;
-    type Air = FreshAir;
-    type Trace = TraceType;
+impl<B: StarkField> Prover for FreshProver<B> {
+    type BaseField = B;
+    type Air = FreshAir<B>;
+    type Trace = TraceType<B>;
 
     // Our public inputs consist of the first and last value in the execution trace.
-    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs {
-        get_pub_inputs(trace)
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs<B> {
+        get_pub_inputs(trace, self.period)
     }
 
     fn options(&self) -> &ProofOptions {
@@ -143,16 +202,31 @@ impl Prover for FreshProver {
     }
 }
 
-fn main() {
-    env_logger::Builder::new()
-        .format(|buf, record| writeln!(buf, "{}", record.args()))
-        .filter_level(log::LevelFilter::Debug)
-        .init();
+/// Same AIR as [`FreshProver`], but its `Trace` is a [`RapTraceTable`] so that the
+/// randomized-AIR auxiliary segment (see `--aux`) can be committed alongside the
+/// main trace.
+pub struct FreshRapProver<B: StarkField> {
+    options: ProofOptions,
+    period: usize,
+}
 
-    let cli = Cli::parse();
+impl<B: StarkField> Prover for FreshRapProver<B> {
+    type BaseField = B;
+    type Air = FreshAir<B>;
+    type Trace = RapTraceTable<B>;
 
+    fn get_pub_inputs(&self, trace: &Self::Trace) -> PublicInputs<B> {
+        trace.get_pub_inputs(self.period)
+    }
+
+    fn options(&self) -> &ProofOptions {
+        &self.options
+    }
+}
+
+fn run<B: FieldTag + From<u128>>(cli: &Cli) {
     let now = Instant::now();
-    let output = prove(&cli);
+    let output = prove::<B>(cli, &cli.input_args);
     debug!(
         "---------------------\nProof generated in {} ms",
         now.elapsed().as_millis()
@@ -162,6 +236,138 @@ fn main() {
     debug!("Proof size: {:.1} KB", proof_bytes.len() as f64 / 1024f64);
     debug!("Proof security: {} bits", output.proof.security_level(true));
 
-    let data = to_data(proof_bytes, output.public_input);
-    confy::store_path(cli.proof_file_path, data).unwrap();
+    if let EnumFormat::Bin = cli.format {
+        write_bin(&cli.proof_file_path, &proof_bytes, &output.public_input);
+    } else {
+        let data = to_data(proof_bytes, output.public_input);
+        save_data(&data, &cli.proof_file_path, cli.format);
+    }
+}
+
+fn run_batch<B: FieldTag + From<u128> + Send + 'static>(cli: &Cli) {
+    let now = Instant::now();
+    let total = cli.batch_start.len();
+
+    // `FreshProver`/`FreshRapProver` are stateless, so each input's trace
+    // generation and proving can run on its own thread, but a proving pass is
+    // CPU/memory-heavy, so concurrency is capped at the number of available
+    // CPUs instead of spawning all `total` threads at once.
+    let concurrency = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+
+    let mut proofs = Vec::with_capacity(total);
+    for (chunk_index, chunk) in cli.batch_start.chunks(concurrency).enumerate() {
+        let base = chunk_index * concurrency;
+        let handles: Vec<_> = chunk
+            .iter()
+            .enumerate()
+            .map(|(offset, &start)| {
+                let n = cli
+                    .batch_n
+                    .get(base + offset)
+                    .copied()
+                    .unwrap_or(cli.input_args.n);
+                let cli = Cli {
+                    proof_file_path: cli.proof_file_path.clone(),
+                    format: cli.format,
+                    field: cli.field,
+                    aux: cli.aux,
+                    period: cli.period,
+                    batch: cli.batch,
+                    batch_start: Vec::new(),
+                    batch_n: Vec::new(),
+                    proof_options: cli.proof_options.clone(),
+                    input_args: InputArg { start, n },
+                };
+                std::thread::spawn(move || {
+                    let item_now = Instant::now();
+                    let output = prove::<B>(&cli, &cli.input_args);
+                    (output, item_now.elapsed())
+                })
+            })
+            .collect();
+
+        for (offset, handle) in handles.into_iter().enumerate() {
+            let i = base + offset;
+            let (output, elapsed) = handle.join().expect("proving thread panicked");
+            debug!(
+                "Proof {}/{} (start={}) generated in {} ms",
+                i + 1,
+                total,
+                cli.batch_start[i],
+                elapsed.as_millis()
+            );
+            proofs.push((output.proof.to_bytes(), output.public_input));
+        }
+    }
+
+    debug!(
+        "---------------------\nGenerated {} proofs in {} ms",
+        proofs.len(),
+        now.elapsed().as_millis()
+    );
+
+    if let EnumFormat::Bin = cli.format {
+        write_bin_batch(&cli.proof_file_path, &proofs);
+    } else {
+        let data = air::BatchData {
+            proofs: proofs
+                .into_iter()
+                .map(|(proof, public_input)| to_data(proof, public_input))
+                .collect(),
+        };
+        air::save_batch_data(&data, &cli.proof_file_path, cli.format);
+    }
+}
+
+fn main() {
+    env_logger::Builder::new()
+        .format(|buf, record| writeln!(buf, "{}", record.args()))
+        .filter_level(log::LevelFilter::Debug)
+        .init();
+
+    let cli = Cli::parse();
+
+    if !cli.batch {
+        if let Err(msg) = validate_period(cli.period, cli.input_args.n) {
+            eprintln!("error: {}", msg);
+            std::process::exit(1);
+        }
+    }
+
+    if cli.batch {
+        if cli.batch_start.is_empty() {
+            eprintln!("error: --batch requires at least one --batch-start value");
+            std::process::exit(1);
+        }
+        if !cli.batch_n.is_empty() && cli.batch_n.len() != cli.batch_start.len() {
+            eprintln!(
+                "error: --batch-n ({} values) must have one entry per --batch-start value ({})",
+                cli.batch_n.len(),
+                cli.batch_start.len()
+            );
+            std::process::exit(1);
+        }
+        for i in 0..cli.batch_start.len() {
+            let n = cli.batch_n.get(i).copied().unwrap_or(cli.input_args.n);
+            if let Err(msg) = validate_period(cli.period, n) {
+                eprintln!("error: batch item {}: {}", i, msg);
+                std::process::exit(1);
+            }
+        }
+
+        match cli.field {
+            EnumField::F62 => run_batch::<air::F62>(&cli),
+            EnumField::F64 => run_batch::<air::F64>(&cli),
+            EnumField::F128 => run_batch::<air::F128>(&cli),
+        }
+        return;
+    }
+
+    match cli.field {
+        EnumField::F62 => run::<air::F62>(&cli),
+        EnumField::F64 => run::<air::F64>(&cli),
+        EnumField::F128 => run::<air::F128>(&cli),
+    }
 }
@@ -4,22 +4,93 @@
 // LICENSE file in the root directory of this source tree.
 
 use std::io::Write;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use log::debug;
+use winter_math::StarkField;
 use winter_prover::StarkProof;
 use winter_verifier::verify;
 
 pub mod air;
-use air::{from_data, Data, FreshAir};
+use air::{
+    from_data, load_batch_data, load_data, read_bin, read_bin_batch, read_bin_batch_field,
+    read_bin_field, Data, EnumField, EnumFormat, FreshAir,
+};
 
-use clap::Parser;
+use clap::{ArgEnum, Parser};
 
 #[derive(Parser)]
 #[clap(name = "verifier", author, version, about, long_about = None)]
 struct Cli {
     #[clap(long, short, display_order = 1, default_value_t = String::from("./stark.toml"))]
     proof_file_path: String,
+    /// On-disk encoding of the proof file being verified.
+    #[clap(long, arg_enum, display_order = 2, default_value_t = EnumFormat::Toml)]
+    format: EnumFormat,
+    /// Verify a `--batch` proof file (an array of proofs) and report
+    /// per-proof and aggregate timing plus a pass/fail count.
+    #[clap(long, display_order = 3)]
+    batch: bool,
+}
+
+fn verify_data<B: StarkField>(data: Data) -> (bool, Option<String>, Duration) {
+    let (pub_inputs, proof_bytes) = from_data::<B>(data);
+    verify_proof::<B>(pub_inputs, proof_bytes)
+}
+
+fn verify_proof<B: StarkField>(
+    pub_inputs: air::PublicInputs<B>,
+    proof_bytes: Vec<u8>,
+) -> (bool, Option<String>, Duration) {
+    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
+    let now = Instant::now();
+    match verify::<FreshAir<B>>(proof, pub_inputs) {
+        Ok(_) => (true, None, now.elapsed()),
+        Err(msg) => (false, Some(msg.to_string()), now.elapsed()),
+    }
+}
+
+fn report(i: usize, total: usize, passed: bool, err: &Option<String>, elapsed: Duration) {
+    match (passed, err) {
+        (true, _) => debug!(
+            "Proof {}/{} verified in {:.1} ms",
+            i + 1,
+            total,
+            elapsed.as_micros() as f64 / 1000f64
+        ),
+        (false, Some(msg)) => debug!(
+            "Proof {}/{} FAILED to verify in {:.1} ms: {}",
+            i + 1,
+            total,
+            elapsed.as_micros() as f64 / 1000f64,
+            msg
+        ),
+        (false, None) => debug!(
+            "Proof {}/{} FAILED to verify in {:.1} ms",
+            i + 1,
+            total,
+            elapsed.as_micros() as f64 / 1000f64
+        ),
+    }
+}
+
+fn run_batch<B: StarkField>(data: Vec<(air::PublicInputs<B>, Vec<u8>)>) {
+    let total = data.len();
+    let now = Instant::now();
+    let mut passed_count = 0;
+    for (i, (pub_inputs, proof_bytes)) in data.into_iter().enumerate() {
+        let (passed, err, elapsed) = verify_proof::<B>(pub_inputs, proof_bytes);
+        report(i, total, passed, &err, elapsed);
+        if passed {
+            passed_count += 1;
+        }
+    }
+    debug!(
+        "---------------------\n{}/{} proofs verified in {} ms",
+        passed_count,
+        total,
+        now.elapsed().as_millis()
+    );
 }
 
 fn main() {
@@ -30,15 +101,64 @@ fn main() {
 
     let cli = Cli::parse();
 
-    let data: Data = confy::load_path(cli.proof_file_path).unwrap();
-    let (pub_inputs, proof_bytes) = from_data(data);
-    let proof = StarkProof::from_bytes(&proof_bytes).unwrap();
-    let now = Instant::now();
-    match verify::<FreshAir>(proof, pub_inputs) {
-        Ok(_) => debug!(
-            "Proof verified in {:.1} ms",
-            now.elapsed().as_micros() as f64 / 1000f64
-        ),
-        Err(msg) => debug!("Failed to verify proof: {}", msg),
+    if cli.batch {
+        if let EnumFormat::Bin = cli.format {
+            match read_bin_batch_field(&cli.proof_file_path) {
+                EnumField::F62 => run_batch(read_bin_batch::<air::F62>(&cli.proof_file_path)),
+                EnumField::F64 => run_batch(read_bin_batch::<air::F64>(&cli.proof_file_path)),
+                EnumField::F128 => run_batch(read_bin_batch::<air::F128>(&cli.proof_file_path)),
+            }
+            return;
+        }
+
+        let batch = load_batch_data(&cli.proof_file_path, cli.format);
+        let total = batch.proofs.len();
+        let now = Instant::now();
+        let mut passed_count = 0;
+        for (i, data) in batch.proofs.into_iter().enumerate() {
+            let (passed, err, elapsed) = match EnumField::from_name(&data.field) {
+                EnumField::F62 => verify_data::<air::F62>(data),
+                EnumField::F64 => verify_data::<air::F64>(data),
+                EnumField::F128 => verify_data::<air::F128>(data),
+            };
+            report(i, total, passed, &err, elapsed);
+            if passed {
+                passed_count += 1;
+            }
+        }
+        debug!(
+            "---------------------\n{}/{} proofs verified in {} ms",
+            passed_count,
+            total,
+            now.elapsed().as_millis()
+        );
+        return;
     }
+
+    if let EnumFormat::Bin = cli.format {
+        let (passed, err, elapsed) = match read_bin_field(&cli.proof_file_path) {
+            EnumField::F62 => {
+                let (pub_inputs, proof) = read_bin::<air::F62>(&cli.proof_file_path);
+                verify_proof(pub_inputs, proof)
+            }
+            EnumField::F64 => {
+                let (pub_inputs, proof) = read_bin::<air::F64>(&cli.proof_file_path);
+                verify_proof(pub_inputs, proof)
+            }
+            EnumField::F128 => {
+                let (pub_inputs, proof) = read_bin::<air::F128>(&cli.proof_file_path);
+                verify_proof(pub_inputs, proof)
+            }
+        };
+        report(0, 1, passed, &err, elapsed);
+        return;
+    }
+
+    let data: Data = load_data(&cli.proof_file_path, cli.format);
+    let (passed, err, elapsed) = match EnumField::from_name(&data.field) {
+        EnumField::F62 => verify_data::<air::F62>(data),
+        EnumField::F64 => verify_data::<air::F64>(data),
+        EnumField::F128 => verify_data::<air::F128>(data),
+    };
+    report(0, 1, passed, &err, elapsed);
 }